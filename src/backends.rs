@@ -0,0 +1,226 @@
+//! Pool of upstream JSON-RPC endpoints with per-backend health tracking.
+//!
+//! `ETH_RPC` may list more than one endpoint, comma-separated, each with an
+//! optional whitespace-separated weight suffix:
+//! `http://a:8545 2,http://b:8545 1,http://c:8545`. A backend with no weight
+//! defaults to `1`. Weight biases routing towards that endpoint when
+//! multiple backends are otherwise equally healthy; it does not override a
+//! backend that is currently degraded or slower.
+//!
+//! The weight separator is whitespace rather than `=`, because `=` legally
+//! appears inside a URL's query string (including with a numeric value,
+//! e.g. `?chainId=1`), which made a parse-based `url=weight` split ambiguous
+//! and silently truncated such URLs. Raw whitespace never appears in a URL.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::latency::PeakEwma;
+
+/// How long a backend that just failed a call is skipped for.
+const DEGRADE_COOLDOWN: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BackendStatus {
+    Ok,
+    Warn,
+    Down,
+}
+
+impl BackendStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendStatus::Ok => "ok",
+            BackendStatus::Warn => "warn",
+            BackendStatus::Down => "down",
+        }
+    }
+}
+
+#[derive(Default)]
+struct BackendHealth {
+    /// Set whenever a call fails; the backend is skipped until this elapses.
+    degraded_until: Option<Instant>,
+    /// Peak-EWMA latency estimator, persisted across requests.
+    latency: PeakEwma,
+}
+
+pub struct Backend {
+    pub url: String,
+    pub weight: u32,
+    health: Mutex<BackendHealth>,
+}
+
+impl Backend {
+    fn new(url: String, weight: u32) -> Self {
+        Backend {
+            url,
+            weight,
+            health: Mutex::new(BackendHealth::default()),
+        }
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        match self.health.lock().unwrap().degraded_until {
+            Some(until) => Instant::now() < until,
+            None => false,
+        }
+    }
+
+    pub fn mark_degraded(&self) {
+        self.health.lock().unwrap().degraded_until = Some(Instant::now() + DEGRADE_COOLDOWN);
+    }
+
+    pub fn mark_recovered(&self) {
+        self.health.lock().unwrap().degraded_until = None;
+    }
+
+    /// Mark a probe as dispatched so a hung request counts as pending
+    /// latency even before it completes or times out.
+    pub fn begin_probe(&self) {
+        self.health.lock().unwrap().latency.begin_probe();
+    }
+
+    /// Record a completed probe's latency and outcome into the backend's
+    /// peak-EWMA estimator.
+    pub fn record_probe(&self, sample_ms: f64, ok: bool) {
+        self.health.lock().unwrap().latency.record(sample_ms, ok);
+    }
+
+    pub fn latency_score(&self) -> f64 {
+        self.health.lock().unwrap().latency.score()
+    }
+
+    pub fn ewma_ms(&self) -> f64 {
+        self.health.lock().unwrap().latency.ewma_ms()
+    }
+
+    pub fn peak_ms(&self) -> f64 {
+        self.health.lock().unwrap().latency.peak_ms()
+    }
+
+    pub fn status(&self, ok_ms: f64, warn_ms: f64) -> BackendStatus {
+        self.health.lock().unwrap().latency.status(ok_ms, warn_ms)
+    }
+
+    /// Lower is better: unmeasured backends are assumed fast so they get a
+    /// chance to be probed. Weight is deliberately *not* folded into this
+    /// value — see [`Backends::ranked`], which only uses it to break near
+    /// ties and never lets it override a real latency difference.
+    fn routing_score(&self) -> f64 {
+        self.latency_score()
+    }
+}
+
+/// Routing scores within this ratio of each other (1.10, i.e. within 10%)
+/// are considered a tie;
+/// weight picks the winner among them. Wide enough to absorb normal EWMA
+/// jitter between two backends that are genuinely equally healthy, narrow
+/// enough that a backend which is actually slower never wins on weight
+/// alone.
+const TIE_EPSILON: f64 = 1.10;
+
+/// The full pool of configured upstream endpoints.
+pub struct Backends {
+    pub backends: Vec<Backend>,
+}
+
+impl Backends {
+    /// Parse the same comma-separated `url[ weight]` syntax used by the
+    /// legacy `ETH_RPC` env var and by `rpc` in the TOML chain config.
+    pub fn from_spec(raw: &str) -> Self {
+        let backends = raw
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|entry| match entry.rsplit_once(char::is_whitespace) {
+                // Only treat this as `url weight` if the suffix actually is
+                // one; whitespace never legally appears inside a URL, so
+                // unlike `=` this split can't collide with the URL's own
+                // query string (e.g. `?chainId=1`).
+                Some((url, weight)) if weight.trim().parse::<u32>().is_ok() => {
+                    Backend::new(url.trim().to_string(), weight.trim().parse().unwrap())
+                }
+                _ => Backend::new(entry.to_string(), 1),
+            })
+            .collect();
+        Backends { backends }
+    }
+
+    /// Healthy (non-degraded) backends ordered best-first by routing score.
+    /// Backends whose scores are within [`TIE_EPSILON`] of each other are
+    /// treated as equally healthy and ordered by weight instead, so weight
+    /// can only break a tie, never flip a real ranking.
+    pub fn ranked(&self) -> Vec<&Backend> {
+        let mut live: Vec<&Backend> = self.backends.iter().filter(|b| !b.is_degraded()).collect();
+        live.sort_by(|a, b| {
+            let (a_score, b_score) = (a.routing_score(), b.routing_score());
+            let tied = a_score <= b_score * TIE_EPSILON && b_score <= a_score * TIE_EPSILON;
+            if tied {
+                b.weight.cmp(&a.weight)
+            } else {
+                a_score.partial_cmp(&b_score).unwrap()
+            }
+        });
+        live
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_spec_defaults_to_weight_one() {
+        let backends = Backends::from_spec("http://a:8545");
+        assert_eq!(backends.backends.len(), 1);
+        assert_eq!(backends.backends[0].url, "http://a:8545");
+        assert_eq!(backends.backends[0].weight, 1);
+    }
+
+    #[test]
+    fn from_spec_parses_explicit_weight() {
+        let backends = Backends::from_spec("http://a:8545 2, http://b:8545 1");
+        assert_eq!(backends.backends[0].url, "http://a:8545");
+        assert_eq!(backends.backends[0].weight, 2);
+        assert_eq!(backends.backends[1].url, "http://b:8545");
+        assert_eq!(backends.backends[1].weight, 1);
+    }
+
+    #[test]
+    fn from_spec_keeps_query_string_equals() {
+        let backends = Backends::from_spec("https://rpc.example.com/v2?apikey=abc123");
+        assert_eq!(backends.backends.len(), 1);
+        assert_eq!(backends.backends[0].url, "https://rpc.example.com/v2?apikey=abc123");
+        assert_eq!(backends.backends[0].weight, 1);
+    }
+
+    #[test]
+    fn from_spec_keeps_query_string_equals_alongside_weighted_entries() {
+        let backends = Backends::from_spec("https://rpc.example.com?apikey=abc123,http://b:8545 3");
+        assert_eq!(backends.backends[0].url, "https://rpc.example.com?apikey=abc123");
+        assert_eq!(backends.backends[0].weight, 1);
+        assert_eq!(backends.backends[1].url, "http://b:8545");
+        assert_eq!(backends.backends[1].weight, 3);
+    }
+
+    /// Regression test for the bug this fix addresses: a numeric query-string
+    /// value (not just a non-numeric one) must not be mistaken for a weight
+    /// suffix and split off, truncating the URL.
+    #[test]
+    fn from_spec_keeps_query_string_with_numeric_value() {
+        let backends = Backends::from_spec("https://rpc.example.com/v2?chainId=1,https://rpc.example.com?id=42");
+        assert_eq!(backends.backends.len(), 2);
+        assert_eq!(backends.backends[0].url, "https://rpc.example.com/v2?chainId=1");
+        assert_eq!(backends.backends[0].weight, 1);
+        assert_eq!(backends.backends[1].url, "https://rpc.example.com?id=42");
+        assert_eq!(backends.backends[1].weight, 1);
+    }
+
+    #[test]
+    fn from_spec_parses_weight_alongside_numeric_query_string() {
+        let backends = Backends::from_spec("https://rpc.example.com/v2?chainId=1 5");
+        assert_eq!(backends.backends[0].url, "https://rpc.example.com/v2?chainId=1");
+        assert_eq!(backends.backends[0].weight, 5);
+    }
+}
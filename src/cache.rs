@@ -0,0 +1,50 @@
+//! Short-TTL cache in front of read-only JSON-RPC calls.
+//!
+//! Without this, every browser tab polling `/api/latest-block` on its own
+//! 15s timer turns into its own `eth_blockNumber` call upstream. Caching by
+//! `{chain}:{method}` collapses concurrent requests within the same TTL
+//! window into a single upstream call, the way web3-proxy caches `/status`
+//! and block data.
+
+use std::future::Future;
+use std::time::Duration;
+
+use moka::future::Cache;
+
+pub struct RpcCache {
+    cache: Cache<String, serde_json::Value>,
+}
+
+impl RpcCache {
+    pub fn from_env(var: &str, default_ms: u64) -> Self {
+        let ttl_ms = std::env::var(var)
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(default_ms);
+        RpcCache {
+            cache: Cache::builder()
+                .time_to_live(Duration::from_millis(ttl_ms))
+                .build(),
+        }
+    }
+
+    /// Cache key for a JSON-RPC method scoped to a chain, e.g.
+    /// `"ethereum:eth_blockNumber"`.
+    pub fn key(chain: &str, method: &str) -> String {
+        format!("{chain}:{method}")
+    }
+}
+
+/// Generic cached-call helper: return the cached value for `key` if still
+/// live, otherwise run `fetch` and populate the cache with its result.
+///
+/// Uses moka's `get_with`, which single-flights concurrent misses on the
+/// same key so that N requests landing on an empty/expired slot collapse
+/// into one upstream call instead of stampeding it.
+pub async fn cached_call<F, Fut>(cache: &RpcCache, key: String, fetch: F) -> serde_json::Value
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = serde_json::Value>,
+{
+    cache.cache.get_with(key, fetch()).await
+}
@@ -0,0 +1,140 @@
+//! TOML-driven chain configuration.
+//!
+//! Each `[[chains]]` entry describes one JSON-RPC node to monitor: its
+//! upstream endpoint(s) (same syntax [`crate::backends::Backends`] parses),
+//! a display name, the probe method used for the block-number check, and
+//! per-chain ok/warn latency thresholds. This lets the dashboard cover any
+//! EVM (or EVM-like) JSON-RPC node without recompiling -- only `eth_syncing`,
+//! `net_peerCount` and `eth_getBlockByNumber` in `/api/health`, and the
+//! `eth_subscribe` newHeads stream, stay Ethereum-specific.
+
+use serde::Deserialize;
+
+#[derive(Deserialize, Clone)]
+pub struct ChainConfig {
+    /// Short key used in the `?chain=` query param and as the Prometheus
+    /// `chain` label; must be unique across the config file -- `load`
+    /// drops (and warns about) any entry whose name repeats an earlier one.
+    pub name: String,
+    #[serde(default = "default_display_name")]
+    pub display_name: String,
+    /// Same comma-separated `url[ weight]` syntax as the legacy `ETH_RPC`.
+    pub rpc: String,
+    #[serde(default = "default_probe_method")]
+    pub probe_method: String,
+    #[serde(default = "default_ok_ms")]
+    pub ok_ms: f64,
+    #[serde(default = "default_warn_ms")]
+    pub warn_ms: f64,
+}
+
+fn default_display_name() -> String {
+    "chain".into()
+}
+
+fn default_probe_method() -> String {
+    "eth_blockNumber".into()
+}
+
+fn default_ok_ms() -> f64 {
+    300.0
+}
+
+fn default_warn_ms() -> f64 {
+    800.0
+}
+
+#[derive(Deserialize, Default)]
+struct ConfigFile {
+    #[serde(default)]
+    chains: Vec<ChainConfig>,
+}
+
+/// Load chain configs from the TOML file at `path`. Falls back to a single
+/// chain built from the legacy `ETH_RPC`/`CHAIN_NAME` env vars when the file
+/// is missing, empty, or fails to parse, so existing `.env`-based
+/// deployments keep working unchanged.
+pub fn load(path: &str) -> Vec<ChainConfig> {
+    match std::fs::read_to_string(path) {
+        Ok(raw) => match toml::from_str::<ConfigFile>(&raw) {
+            Ok(cfg) if !cfg.chains.is_empty() => dedup_by_name(cfg.chains),
+            Ok(_) => {
+                tracing::warn!(path, "config file has no [[chains]] entries, falling back to ETH_RPC");
+                vec![legacy_chain()]
+            }
+            Err(err) => {
+                tracing::warn!(path, %err, "failed to parse chain config, falling back to ETH_RPC");
+                vec![legacy_chain()]
+            }
+        },
+        Err(_) => vec![legacy_chain()],
+    }
+}
+
+/// Drop chains whose `name` repeats one seen earlier in the file, warning
+/// about each one dropped. `name` is used as the key of the `HashMap` the
+/// chains end up in, so a duplicate would otherwise silently stop
+/// monitoring whichever entry lost the collision; keeping the first
+/// occurrence also keeps `chain_configs[0]` -- the default chain -- stable.
+fn dedup_by_name(chains: Vec<ChainConfig>) -> Vec<ChainConfig> {
+    let mut seen = std::collections::HashSet::new();
+    chains
+        .into_iter()
+        .filter(|chain| {
+            let first_occurrence = seen.insert(chain.name.clone());
+            if !first_occurrence {
+                tracing::warn!(name = %chain.name, "duplicate chain name in config, skipping");
+            }
+            first_occurrence
+        })
+        .collect()
+}
+
+fn legacy_chain() -> ChainConfig {
+    let chain_name = std::env::var("CHAIN_NAME").unwrap_or_else(|_| "default".into());
+    ChainConfig {
+        name: chain_name.clone(),
+        display_name: chain_name,
+        rpc: std::env::var("ETH_RPC").unwrap_or_else(|_| "http://127.0.0.1:8545".into()),
+        probe_method: default_probe_method(),
+        ok_ms: default_ok_ms(),
+        warn_ms: default_warn_ms(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(name: &str, rpc: &str) -> ChainConfig {
+        ChainConfig {
+            name: name.into(),
+            display_name: default_display_name(),
+            rpc: rpc.into(),
+            probe_method: default_probe_method(),
+            ok_ms: default_ok_ms(),
+            warn_ms: default_warn_ms(),
+        }
+    }
+
+    #[test]
+    fn dedup_by_name_keeps_unique_entries() {
+        let chains = vec![chain("ethereum", "http://a"), chain("polygon", "http://b")];
+        let deduped = dedup_by_name(chains);
+        assert_eq!(deduped.len(), 2);
+    }
+
+    #[test]
+    fn dedup_by_name_drops_later_duplicates_and_keeps_the_first() {
+        let chains = vec![
+            chain("ethereum", "http://a"),
+            chain("polygon", "http://b"),
+            chain("ethereum", "http://c"),
+        ];
+        let deduped = dedup_by_name(chains);
+        assert_eq!(deduped.len(), 2);
+        assert_eq!(deduped[0].name, "ethereum");
+        assert_eq!(deduped[0].rpc, "http://a");
+        assert_eq!(deduped[1].name, "polygon");
+    }
+}
@@ -0,0 +1,107 @@
+//! Multi-signal node health, beyond raw RTT: sync status, peer count, and
+//! how stale the chain head is. Mirrors the approach the openethereum dapps
+//! node-health module takes -- a node can answer `eth_blockNumber` quickly
+//! while still being unsynced, peerless, or stuck on an old head.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{hex_to_u64, rpc_call, AppState, ChainQuery};
+
+/// Head older than this is considered merely stale.
+const STALE_WARN_SECS: u64 = 120;
+/// Head older than this means the chain looks stuck.
+const STALE_DOWN_SECS: u64 = 600;
+
+#[derive(Serialize, Clone)]
+pub struct HealthResp {
+    syncing: bool,
+    highest_block: Option<u64>,
+    current_block: Option<u64>,
+    peers: Option<u64>,
+    head_age_secs: Option<u64>,
+    status: &'static str, // "ok" | "warn" | "down"
+}
+
+pub async fn health_handler(
+    State(state): State<AppState>,
+    Query(q): Query<ChainQuery>,
+) -> Json<HealthResp> {
+    let none = HealthResp {
+        syncing: false,
+        highest_block: None,
+        current_block: None,
+        peers: None,
+        head_age_secs: None,
+        status: "down",
+    };
+
+    let Some(chain) = state.resolve_chain(q.chain.as_deref()) else {
+        return Json(none);
+    };
+    let Some(backend) = chain.backends.ranked().into_iter().next() else {
+        return Json(none.clone());
+    };
+
+    let (syncing, highest_block, current_block) =
+        match rpc_call(&state.http, &backend.url, "eth_syncing", vec![]).await {
+            Ok(Value::Bool(false)) => (false, None, None),
+            Err(err) => {
+                tracing::warn!(url = %backend.url, %err, "eth_syncing failed");
+                (false, None, None)
+            }
+            Ok(v) => (
+                true,
+                v.get("highestBlock").and_then(Value::as_str).and_then(|s| hex_to_u64(s).ok()),
+                v.get("currentBlock").and_then(Value::as_str).and_then(|s| hex_to_u64(s).ok()),
+            ),
+        };
+
+    let peers = rpc_call(&state.http, &backend.url, "net_peerCount", vec![])
+        .await
+        .map_err(|err| tracing::warn!(url = %backend.url, %err, "net_peerCount failed"))
+        .ok()
+        .and_then(|v| v.as_str().and_then(|s| hex_to_u64(s).ok()));
+
+    let head_age_secs = rpc_call(
+        &state.http,
+        &backend.url,
+        "eth_getBlockByNumber",
+        vec![json!("latest"), json!(false)],
+    )
+    .await
+    .map_err(|err| tracing::warn!(url = %backend.url, %err, "eth_getBlockByNumber failed"))
+    .ok()
+    .and_then(|v| v.get("timestamp").and_then(Value::as_str).and_then(|s| hex_to_u64(s).ok()))
+    .map(|block_ts| {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        now.saturating_sub(block_ts)
+    });
+
+    let status = classify(syncing, peers, head_age_secs);
+
+    Json(HealthResp {
+        syncing,
+        highest_block,
+        current_block,
+        peers,
+        head_age_secs,
+        status,
+    })
+}
+
+fn classify(syncing: bool, peers: Option<u64>, head_age_secs: Option<u64>) -> &'static str {
+    if peers == Some(0) {
+        return "down";
+    }
+    match head_age_secs {
+        None => "down",
+        Some(age) if age > STALE_DOWN_SECS => "down",
+        Some(age) if age > STALE_WARN_SECS || syncing => "warn",
+        Some(_) => "ok",
+    }
+}
@@ -0,0 +1,186 @@
+//! Peak-EWMA latency scoring, Finagle/web3-proxy style.
+//!
+//! Each backend keeps a running exponentially-weighted moving average of its
+//! observed round-trip times plus a "peak" that jumps up immediately on a
+//! slow or failed probe and decays back down over [`PEAK_HALF_LIFE_SECS`].
+//! The reported score is `max(ewma, decayed_peak, pending)`, where `pending`
+//! is how long the current in-flight probe (if any) has been outstanding —
+//! so a hung node scores badly even before it times out, with no magic
+//! sentinel latency required.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use crate::backends::BackendStatus;
+
+/// Smoothing factor applied to each new sample: closer to 1.0 means the
+/// average reacts more slowly to a single probe.
+const EWMA_ALPHA: f64 = 0.7;
+/// How quickly a spike in the peak term decays back towards the EWMA.
+const PEAK_HALF_LIFE_SECS: f64 = 10.0;
+/// Latency attributed to a failed/timed-out probe when bumping the peak.
+const FAILURE_PENALTY_MS: f64 = 2000.0;
+/// Number of recent probe outcomes kept to compute a failure ratio.
+const FAILURE_WINDOW: usize = 20;
+
+pub struct PeakEwma {
+    ewma_ms: Option<f64>,
+    peak_ms: f64,
+    peak_set_at: Instant,
+    inflight_started: Option<Instant>,
+    recent_ok: VecDeque<bool>,
+}
+
+impl Default for PeakEwma {
+    fn default() -> Self {
+        PeakEwma {
+            ewma_ms: None,
+            peak_ms: 0.0,
+            peak_set_at: Instant::now(),
+            inflight_started: None,
+            recent_ok: VecDeque::with_capacity(FAILURE_WINDOW),
+        }
+    }
+}
+
+impl PeakEwma {
+    /// Call right before dispatching a probe so a hung request still shows
+    /// up in `score()` even though no sample has completed yet.
+    pub fn begin_probe(&mut self) {
+        self.inflight_started = Some(Instant::now());
+    }
+
+    /// Record the outcome of a completed probe: its observed latency and
+    /// whether it succeeded.
+    pub fn record(&mut self, sample_ms: f64, ok: bool) {
+        self.inflight_started = None;
+
+        self.ewma_ms = Some(match self.ewma_ms {
+            Some(prev) => prev * EWMA_ALPHA + sample_ms * (1.0 - EWMA_ALPHA),
+            None => sample_ms,
+        });
+
+        let spike = if ok { sample_ms } else { FAILURE_PENALTY_MS };
+        if spike >= self.decayed_peak() {
+            self.peak_ms = spike;
+            self.peak_set_at = Instant::now();
+        }
+
+        if self.recent_ok.len() == FAILURE_WINDOW {
+            self.recent_ok.pop_front();
+        }
+        self.recent_ok.push_back(ok);
+    }
+
+    fn decayed_peak(&self) -> f64 {
+        let elapsed = self.peak_set_at.elapsed().as_secs_f64();
+        self.peak_ms * 0.5f64.powf(elapsed / PEAK_HALF_LIFE_SECS)
+    }
+
+    fn pending_ms(&self) -> f64 {
+        self.inflight_started
+            .map(|t| t.elapsed().as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+
+    /// `max(ewma, decayed peak, pending in-flight latency)` — the value used
+    /// both for classification and for routing between backends.
+    pub fn score(&self) -> f64 {
+        let ewma = self.ewma_ms.unwrap_or(0.0);
+        ewma.max(self.decayed_peak()).max(self.pending_ms())
+    }
+
+    pub fn failure_ratio(&self) -> f64 {
+        if self.recent_ok.is_empty() {
+            return 0.0;
+        }
+        let failed = self.recent_ok.iter().filter(|ok| !**ok).count();
+        failed as f64 / self.recent_ok.len() as f64
+    }
+
+    pub fn ewma_ms(&self) -> f64 {
+        self.ewma_ms.unwrap_or(f64::NAN)
+    }
+
+    pub fn peak_ms(&self) -> f64 {
+        self.decayed_peak()
+    }
+
+    /// Classify from the peak-EWMA score plus the recent failure ratio so a
+    /// backend that is merely slow isn't conflated with one that's flapping.
+    /// Thresholds are per-chain (see [`crate::config::ChainConfig`]) rather
+    /// than fixed, since an acceptable latency varies by network.
+    pub fn status(&self, ok_ms: f64, warn_ms: f64) -> BackendStatus {
+        if self.ewma_ms.is_none() {
+            return BackendStatus::Down;
+        }
+        if self.failure_ratio() > 0.5 {
+            return BackendStatus::Down;
+        }
+        let score = self.score();
+        if score <= ok_ms && self.failure_ratio() == 0.0 {
+            BackendStatus::Ok
+        } else if score <= warn_ms {
+            BackendStatus::Warn
+        } else {
+            BackendStatus::Down
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_is_down_before_any_sample() {
+        let ewma = PeakEwma::default();
+        assert_eq!(ewma.status(100.0, 300.0), BackendStatus::Down);
+    }
+
+    #[test]
+    fn status_is_ok_when_fast_and_healthy() {
+        let mut ewma = PeakEwma::default();
+        ewma.record(50.0, true);
+        assert_eq!(ewma.status(100.0, 300.0), BackendStatus::Ok);
+    }
+
+    #[test]
+    fn status_is_warn_between_ok_and_warn_thresholds() {
+        let mut ewma = PeakEwma::default();
+        ewma.record(50.0, true);
+        ewma.record(200.0, true);
+        assert_eq!(ewma.status(100.0, 300.0), BackendStatus::Warn);
+    }
+
+    #[test]
+    fn status_is_down_above_warn_threshold() {
+        let mut ewma = PeakEwma::default();
+        ewma.record(500.0, true);
+        assert_eq!(ewma.status(100.0, 300.0), BackendStatus::Down);
+    }
+
+    #[test]
+    fn status_is_down_when_failure_ratio_exceeds_half_even_if_fast() {
+        let mut ewma = PeakEwma::default();
+        ewma.record(10.0, true);
+        ewma.record(10.0, false);
+        ewma.record(10.0, false);
+        assert!(ewma.failure_ratio() > 0.5);
+        // Thresholds wide enough that latency alone would pass, so only the
+        // failure-ratio branch can be responsible for Down here.
+        assert_eq!(ewma.status(5000.0, 5000.0), BackendStatus::Down);
+    }
+
+    #[test]
+    fn status_is_warn_not_ok_at_exactly_half_failure_ratio() {
+        let mut ewma = PeakEwma::default();
+        ewma.record(10.0, true);
+        ewma.record(10.0, false);
+        assert_eq!(ewma.failure_ratio(), 0.5);
+        // A 50% failure ratio isn't "> 0.5" so it doesn't force Down, but it
+        // isn't 0.0 either, so Ok (which requires a clean record) is ruled
+        // out even though the score is well under ok_ms.
+        assert_eq!(ewma.status(5000.0, 5000.0), BackendStatus::Warn);
+    }
+}
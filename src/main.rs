@@ -1,17 +1,54 @@
-use axum::{routing::{get}, Router, extract::State, Json};
-use serde::{Deserialize, Serialize};
-use std::{net::SocketAddr, sync::Arc, time::Instant};
-use tera::{Tera, Context};
+mod backends;
+mod cache;
+mod config;
+mod health;
+mod latency;
+mod metrics;
+mod stream;
+
+use axum::{extract::{Query, State}, routing::get, Json, Router};
 use axum::response::Html;
+use backends::Backends;
+use cache::{cached_call, RpcCache};
+use config::ChainConfig;
+use metrics::Metrics;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Instant};
+use stream::HeadStream;
+use tera::{Context, Tera};
 use tokio::net::TcpListener;
 
+/// Per-chain state: its backend pool, thresholds, and SSE broadcaster.
+pub(crate) struct ChainState {
+    pub config: ChainConfig,
+    pub backends: Arc<Backends>,
+    pub stream: Arc<HeadStream>,
+}
+
 #[derive(Clone)]
-struct AppState {
+pub(crate) struct AppState {
     tera: Arc<Tera>,
-    http: Client,
-    rpc_url: String,
-    chain_name: String,
+    pub(crate) http: Client,
+    chains: Arc<HashMap<String, Arc<ChainState>>>,
+    default_chain: String,
+    cache: Arc<RpcCache>,
+    pub(crate) metrics: Arc<Metrics>,
+}
+
+impl AppState {
+    /// Resolve a `?chain=` query param to its `ChainState`, falling back to
+    /// the first configured chain when none is given.
+    pub(crate) fn resolve_chain(&self, requested: Option<&str>) -> Option<Arc<ChainState>> {
+        let key = requested.unwrap_or(&self.default_chain);
+        self.chains.get(key).cloned()
+    }
+
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ChainQuery {
+    pub(crate) chain: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -31,56 +68,132 @@ struct JsonRpcResp {
 }
 
 #[derive(Serialize)]
-struct LatencyResp {
-    p50_ms: f64,
-    p95_ms: f64,
-    samples: usize,
+struct BackendLatency {
+    url: String,
     status: &'static str, // "ok" | "warn" | "down"
+    ewma_ms: f64,
+    peak_ms: f64,
+    score_ms: f64,
+}
+
+/// Either a transport-level failure (connection refused, non-2xx, bad body)
+/// or a well-formed JSON-RPC response carrying an `error` field.
+#[derive(Debug)]
+pub(crate) enum RpcCallError {
+    Transport(reqwest::Error),
+    Rpc(serde_json::Value),
+}
+
+impl From<reqwest::Error> for RpcCallError {
+    fn from(err: reqwest::Error) -> Self {
+        RpcCallError::Transport(err)
+    }
+}
+
+impl RpcCallError {
+    /// Short label for logs and the `web3_probe_failures_total{kind=...}`
+    /// metric -- lets an operator tell "node is down" apart from "node is up
+    /// but rejected this call" without parsing the message.
+    pub(crate) fn kind(&self) -> &'static str {
+        match self {
+            RpcCallError::Transport(_) => "transport",
+            RpcCallError::Rpc(_) => "rpc",
+        }
+    }
+}
+
+impl std::fmt::Display for RpcCallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RpcCallError::Transport(err) => write!(f, "transport error: {err}"),
+            RpcCallError::Rpc(err) => write!(f, "rpc error: {err}"),
+        }
+    }
 }
 
-async fn rpc_block_number(client: &reqwest::Client, rpc_url: &str) -> Result<(), reqwest::Error> {
+/// Generic JSON-RPC call. The probe method is per-chain config (see
+/// [`config::ChainConfig::probe_method`]) rather than a hardcoded
+/// `eth_blockNumber`, so the same helper drives the block-number probe,
+/// `eth_syncing`, `net_peerCount`, and `eth_getBlockByNumber`. The
+/// `eth_subscribe` call in [`stream`] goes over the websocket instead.
+pub(crate) async fn rpc_call(
+    client: &reqwest::Client,
+    rpc_url: &str,
+    method: &str,
+    params: Vec<serde_json::Value>,
+) -> Result<serde_json::Value, RpcCallError> {
     let body = JsonRpcReq {
         jsonrpc: "2.0",
-        method: "eth_blockNumber",
-        params: vec![],
+        method,
+        params,
         id: 1,
     };
-    client.post(rpc_url).json(&body).send().await?.error_for_status()?;
-    Ok(())
+    let resp: JsonRpcResp = client
+        .post(rpc_url)
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    // HTTP 200 with a JSON-RPC `error` field (e.g. an unsupported method
+    // like `net_peerCount` on a public endpoint) is an application-level
+    // failure, not a successful `null` result -- treat it as such.
+    if let Some(error) = resp.error {
+        return Err(RpcCallError::Rpc(error));
+    }
+    Ok(resp.result.unwrap_or(serde_json::Value::Null))
 }
 
-fn percentile(v: &mut [f64], p: f64) -> f64 {
-    v.sort_by(|a, b| a.partial_cmp(b).unwrap());
-    if v.is_empty() { return f64::NAN; }
-    let idx = ((p * (v.len() as f64 - 1.0)).round() as usize).min(v.len() - 1);
-    v[idx]
+fn build_chain_state(cfg: ChainConfig) -> Arc<ChainState> {
+    Arc::new(ChainState {
+        backends: Arc::new(Backends::from_spec(&cfg.rpc)),
+        stream: Arc::new(HeadStream::new()),
+        config: cfg,
+    })
 }
 
-
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt()
-        .with_env_filter("info")
-        .init();
+    tracing_subscriber::fmt().with_env_filter("info").init();
 
     dotenvy::dotenv().ok();
-    let rpc_url = std::env::var("ETH_RPC").unwrap_or_else(|_| "http://127.0.0.1:8545".into());
+
+    let config_path = std::env::var("CHAINS_CONFIG").unwrap_or_else(|_| "chains.toml".into());
+    let chain_configs = config::load(&config_path);
+    let default_chain = chain_configs[0].name.clone();
+
+    let cache = RpcCache::from_env("CACHE_TTL_MS", 2000);
+    let metrics = Metrics::new();
 
     let mut tera = Tera::default();
     tera.add_raw_template("index.html", INDEX_HTML).expect("template");
-    let chain_name = std::env::var("CHAIN_NAME").unwrap_or_else(|_| "unknown".into());
+
+    let chains: HashMap<String, Arc<ChainState>> = chain_configs
+        .into_iter()
+        .map(|cfg| (cfg.name.clone(), build_chain_state(cfg)))
+        .collect();
 
     let state = AppState {
         tera: Arc::new(tera),
         http: Client::new(),
-        rpc_url,
-        chain_name,
+        chains: Arc::new(chains),
+        default_chain,
+        cache: Arc::new(cache),
+        metrics: Arc::new(metrics),
     };
 
+    for chain in state.chains.values() {
+        tokio::spawn(stream::run_head_subscriber(state.clone(), chain.clone()));
+    }
+
     let app = Router::new()
         .route("/", get(index))
         .route("/api/latest-block", get(latest_block))
         .route("/api/node-latency", get(node_latency))
+        .route("/api/stream", get(stream::stream_handler))
+        .route("/api/health", get(health::health_handler))
+        .route("/metrics", get(metrics::metrics_handler))
         .with_state(state);
 
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
@@ -89,78 +202,122 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-fn hex_to_u64(hex: &str) -> Result<u64, std::num::ParseIntError> {
+pub(crate) fn hex_to_u64(hex: &str) -> Result<u64, std::num::ParseIntError> {
     let trimmed = hex.trim_start_matches("0x");
     u64::from_str_radix(trimmed, 16)
 }
 
-async fn node_latency(State(state): State<AppState>) -> Json<LatencyResp> {
-    // do N serial calls; simple & stable
-    let n = 7usize;
-    let mut samples = Vec::with_capacity(n);
-    for _ in 0..n {
+async fn node_latency(
+    State(state): State<AppState>,
+    Query(q): Query<ChainQuery>,
+) -> Json<Vec<BackendLatency>> {
+    let Some(chain) = state.resolve_chain(q.chain.as_deref()) else {
+        return Json(vec![]);
+    };
+
+    // One probe per backend per request; the peak-EWMA estimator on each
+    // backend already carries the history, so there's no need to burst N
+    // fresh samples every time this is called.
+    let mut out = Vec::with_capacity(chain.backends.backends.len());
+
+    for backend in &chain.backends.backends {
+        backend.begin_probe();
         let t0 = Instant::now();
-        // ignore individual errors; treat as slow/down
-        let ok = rpc_block_number(&state.http, &state.rpc_url).await.is_ok();
+        let result = rpc_call(&state.http, &backend.url, &chain.config.probe_method, vec![]).await;
         let ms = t0.elapsed().as_secs_f64() * 1000.0;
-        // if failed, record a large sentinel (3s)
-        samples.push(if ok { ms } else { 3000.0 });
+        let fail_kind = result.as_ref().err().map(RpcCallError::kind);
+        if let Err(err) = &result {
+            tracing::warn!(url = %backend.url, %err, "probe failed");
+        }
+        backend.record_probe(ms, result.is_ok());
+
+        let status = backend.status(chain.config.ok_ms, chain.config.warn_ms);
+        let up = status != backends::BackendStatus::Down;
+        if up {
+            backend.mark_recovered();
+        } else {
+            backend.mark_degraded();
+        }
+        state
+            .metrics
+            .observe_probe(&backend.url, backend.ewma_ms(), backend.peak_ms(), up, fail_kind);
+
+        out.push(BackendLatency {
+            url: backend.url.clone(),
+            status: status.as_str(),
+            ewma_ms: backend.ewma_ms(),
+            peak_ms: backend.peak_ms(),
+            score_ms: backend.latency_score(),
+        });
     }
 
-    let mut s = samples.clone();
-    let p50 = percentile(&mut s, 0.50);
-    let p95 = percentile(&mut s, 0.95);
-
-    // thresholds (tune as you like)
-    // ok:   p95 <= 300ms
-    // warn: 300ms < p95 <= 800ms
-    // down: p95  > 800ms (or many failures)
-    let status = if p95.is_nan() {
-        "down"
-    } else if p95 <= 300.0 {
-        "ok"
-    } else if p95 <= 800.0 {
-        "warn"
-    } else {
-        "down"
-    };
-
-    Json(LatencyResp { p50_ms: p50, p95_ms: p95, samples: n, status })
+    Json(out)
 }
 
+#[derive(Serialize)]
+struct ChainSummary {
+    name: String,
+    display_name: String,
+}
 
 async fn index(State(state): State<AppState>) -> Html<String> {
+    let chains: Vec<ChainSummary> = state
+        .chains
+        .values()
+        .map(|c| ChainSummary {
+            name: c.config.name.clone(),
+            display_name: c.config.display_name.clone(),
+        })
+        .collect();
+
     let mut ctx = Context::new();
     ctx.insert("title", "Web3 Node Current Block Status");
-    ctx.insert("chain_name", &state.chain_name);
+    ctx.insert("chains", &chains);
     let html = state.tera.render("index.html", &ctx).unwrap();
     Html(html)
 }
 
-async fn latest_block(State(state): State<AppState>) -> Json<serde_json::Value> {
-    let body = JsonRpcReq {
-        jsonrpc: "2.0",
-        method: "eth_blockNumber",
-        params: vec![],
-        id: 1,
+async fn latest_block(
+    State(state): State<AppState>,
+    Query(q): Query<ChainQuery>,
+) -> Json<serde_json::Value> {
+    let Some(chain) = state.resolve_chain(q.chain.as_deref()) else {
+        return Json(serde_json::json!({ "error": "unknown chain" }));
     };
-
-    let resp: JsonRpcResp = state.http.post(&state.rpc_url)
-        .json(&body)
-        .send().await.unwrap()
-        .json().await.unwrap();
-
-    let hex = resp.result.unwrap_or(serde_json::Value::String("0x0".into()));
-    let block_str = hex.as_str().unwrap_or("0x0");
-    let block_num = hex_to_u64(block_str).unwrap_or(0);
-
-    Json(serde_json::json!({
-    "blockNumberHex": block_str,
-    "blockNumber": block_num,
-    "chain": state.chain_name,
-    }))
+    let key = RpcCache::key(&chain.config.name, &chain.config.probe_method);
+    let value = cached_call(&state.cache, key, || fetch_latest_block(state.clone(), chain.clone())).await;
+    Json(value)
 }
 
+pub(crate) async fn fetch_latest_block(state: AppState, chain: Arc<ChainState>) -> serde_json::Value {
+    for backend in chain.backends.ranked() {
+        let result = match rpc_call(&state.http, &backend.url, &chain.config.probe_method, vec![]).await {
+            Ok(r) => r,
+            Err(err) => {
+                tracing::warn!(url = %backend.url, %err, "backend failed, trying next");
+                backend.mark_degraded();
+                continue;
+            }
+        };
+        backend.mark_recovered();
+
+        let block_str = result.as_str().unwrap_or("0x0").to_string();
+        let block_num = hex_to_u64(&block_str).unwrap_or(0);
+        state.metrics.set_latest_block(&chain.config.name, block_num);
+
+        return serde_json::json!({
+            "blockNumberHex": block_str,
+            "blockNumber": block_num,
+            "chain": chain.config.name,
+            "backend": backend.url,
+        });
+    }
+
+    serde_json::json!({
+        "error": "all backends unavailable",
+        "chain": chain.config.name,
+    })
+}
 
 const INDEX_HTML: &str = r##"<!doctype html>
 <html>
@@ -168,6 +325,7 @@ const INDEX_HTML: &str = r##"<!doctype html>
   <meta charset="utf-8">
   <title>Web3 Node Current Block Status</title>
   <script src="https://unpkg.com/htmx.org@1.9.12"></script>
+  <script src="https://unpkg.com/htmx.org@1.9.12/dist/ext/sse.js"></script>
   <style>
     body { font-family: system-ui, -apple-system, Segoe UI, Roboto, Arial; margin: 2rem; }
     .card { max-width: 720px; padding: 1rem 1.5rem; border: 1px solid #e5e7eb; border-radius: 12px; background: #fff; margin-bottom: 1rem; }
@@ -179,44 +337,51 @@ const INDEX_HTML: &str = r##"<!doctype html>
     .warn { background:#fffbeb; color:#92400e; border-color:#fde68a; }   /* yellow */
     .down { background:#fef2f2; color:#991b1b; border-color:#fecaca; }   /* red */
     small { color:#6b7280; }
+    h2 { font-size: 1.1rem; margin: 2rem 0 .5rem; }
   </style>
 </head>
 <body>
-  <h1>{{ title }} ({{ chain_name }})</h1>
+  <h1>{{ title }}</h1>
+
+  {% for chain in chains %}
+  <h2>{{ chain.display_name }}
+    <span id="overall-badge-{{ chain.name }}" class="badge down" style="font-size:.7rem; vertical-align:middle;">checking…</span>
+  </h2>
 
   <!-- Block Number Card -->
-  <div class="card">
-    <p>Click or wait — auto-refreshes every 15s from your ETH_RPC.</p>
+  <div class="card" hx-ext="sse" sse-connect="/api/stream?chain={{ chain.name }}">
+    <p>Live via SSE when the backend supports <code>eth_subscribe</code>; falls back to polling every 15s otherwise.</p>
+
+    <!-- pushed on every new head; also covers the fallback-to-polling case,
+         since /api/stream emits the same shape on its polling cadence -->
+    <div sse-swap="message" hx-target="#out-{{ chain.name }}" hx-swap="innerHTML"></div>
 
-    <!-- auto poller -->
+    <!-- auto poller: kept as a belt-and-braces refresh alongside SSE -->
     <div
-      hx-get="/api/latest-block"
+      hx-get="/api/latest-block?chain={{ chain.name }}"
       hx-trigger="load, every 15s"
-      hx-target="#out"
+      hx-target="#out-{{ chain.name }}"
       hx-swap="innerHTML">
     </div>
 
     <button class="btn"
-      hx-get="/api/latest-block"
-      hx-target="#out"
+      hx-get="/api/latest-block?chain={{ chain.name }}"
+      hx-target="#out-{{ chain.name }}"
       hx-swap="innerHTML">
       Get Latest Block
     </button>
 
-    <pre id="out" class="mono" style="margin-top: 1rem;">(loading…)</pre>
-    <small id="ts"></small>
+    <pre id="out-{{ chain.name }}" class="mono" style="margin-top: 1rem;">(loading…)</pre>
+    <small id="ts-{{ chain.name }}"></small>
   </div>
 
   <!-- Latency Card -->
   <div class="card">
     <div class="row" style="justify-content: space-between;">
-      <div class="row">
-        <strong>Node Latency</strong>
-        <span id="status-badge" class="badge down">checking…</span>
-      </div>
+      <strong>Node Latency</strong>
       <button class="btn"
-        hx-get="/api/node-latency"
-        hx-target="#latency-json"
+        hx-get="/api/node-latency?chain={{ chain.name }}"
+        hx-target="#latency-json-{{ chain.name }}"
         hx-swap="innerHTML">
         Probe Now
       </button>
@@ -224,52 +389,147 @@ const INDEX_HTML: &str = r##"<!doctype html>
 
     <!-- auto poll latency every 7s -->
     <div
-      hx-get="/api/node-latency"
+      hx-get="/api/node-latency?chain={{ chain.name }}"
       hx-trigger="load, every 7s"
-      hx-target="#latency-json"
+      hx-target="#latency-json-{{ chain.name }}"
       hx-swap="innerHTML">
     </div>
 
-    <!-- raw JSON lands here (hidden); script parses and renders pretty text -->
-    <pre id="latency-json" class="mono" style="display:none;"></pre>
+    <!-- raw JSON lands here (hidden); script parses and renders one badge per backend -->
+    <pre id="latency-json-{{ chain.name }}" class="mono latency-json" data-chain="{{ chain.name }}" style="display:none;"></pre>
 
-    <div class="mono" style="margin-top: .75rem;">
-      p50: <span id="lat-p50">—</span> ms,
-      p95: <span id="lat-p95">—</span> ms
+    <div id="backend-rows-{{ chain.name }}"></div>
+    <small id="lat-ts-{{ chain.name }}"></small>
+  </div>
+
+  <!-- Health Card -->
+  <div class="card">
+    <div class="row" style="justify-content: space-between;">
+      <strong>Node Health</strong>
+      <span id="health-badge-{{ chain.name }}" class="badge down">checking…</span>
+    </div>
+
+    <!-- auto poll health every 20s -->
+    <div
+      hx-get="/api/health?chain={{ chain.name }}"
+      hx-trigger="load, every 20s"
+      hx-target="#health-json-{{ chain.name }}"
+      hx-swap="innerHTML">
     </div>
-    <small id="lat-ts"></small>
+
+    <!-- raw JSON lands here (hidden); script parses and renders the summary -->
+    <pre id="health-json-{{ chain.name }}" class="mono health-json" data-chain="{{ chain.name }}" style="display:none;"></pre>
+
+    <div class="mono" id="health-summary-{{ chain.name }}" style="margin-top: .75rem;">—</div>
   </div>
+  {% endfor %}
 
   <script>
-    // Update timestamp when latest-block swaps
+    // Pull the `chain` query param out of an htmx response URL
+    function chainOf(xhr) {
+      try {
+        return new URL(xhr.responseURL).searchParams.get('chain');
+      } catch (e) {
+        return null;
+      }
+    }
+
+    // Worst-of(latency, health) per chain, folded into one overall badge so
+    // a node that looks fine on latency but is reporting unhealthy (or vice
+    // versa) doesn't show two badges with nothing tying them together.
+    const STATUS_RANK = { ok: 0, warn: 1, down: 2 };
+    const chainSignals = {};
+
+    function worstStatus(a, b) {
+      if (a == null) return b;
+      if (b == null) return a;
+      return STATUS_RANK[a] >= STATUS_RANK[b] ? a : b;
+    }
+
+    function updateOverallBadge(chain) {
+      const signals = chainSignals[chain] || {};
+      const overall = worstStatus(signals.latency, signals.health);
+      const badge = document.getElementById('overall-badge-' + chain);
+      if (badge && overall) {
+        badge.classList.remove('ok', 'warn', 'down');
+        badge.classList.add(overall);
+        badge.textContent = overall.toUpperCase();
+      }
+    }
+
+    // Update timestamp when a latest-block card swaps
     document.body.addEventListener('htmx:afterSwap', function (evt) {
-      if (evt.target && evt.target.id === 'out') {
-        document.getElementById('ts').textContent =
-          'Last updated: ' + new Date().toLocaleTimeString();
+      if (evt.target && evt.target.id && evt.target.id.startsWith('out-')) {
+        const chain = evt.target.id.slice('out-'.length);
+        const ts = document.getElementById('ts-' + chain);
+        if (ts) ts.textContent = 'Last updated: ' + new Date().toLocaleTimeString();
       }
     });
 
-    // Parse latency JSON and update UI
+    // Parse latency JSON (one entry per backend) and render a badge per row
     document.body.addEventListener('htmx:afterOnLoad', function (evt) {
       try {
         const url = evt.detail.xhr.responseURL || '';
         if (!url.includes('/api/node-latency')) return;
+        const chain = chainOf(evt.detail.xhr);
+        if (!chain) return;
+
+        const backends = JSON.parse(evt.detail.xhr.responseText);
+        const rows = document.getElementById('backend-rows-' + chain);
+        if (!rows) return;
+        rows.innerHTML = '';
+        backends.forEach(function (b) {
+          const row = document.createElement('div');
+          row.className = 'row mono';
+          row.style.marginTop = '.5rem';
+          row.innerHTML =
+            '<span class="badge ' + b.status + '">' + b.status.toUpperCase() + '</span>' +
+            '<span>' + b.url + '</span>' +
+            '<span>ewma: ' + b.ewma_ms.toFixed(1) + 'ms</span>' +
+            '<span>score: ' + b.score_ms.toFixed(1) + 'ms</span>';
+          rows.appendChild(row);
+        });
+
+        const ts = document.getElementById('lat-ts-' + chain);
+        if (ts) ts.textContent = 'Last probe: ' + new Date().toLocaleTimeString();
+
+        chainSignals[chain] = chainSignals[chain] || {};
+        chainSignals[chain].latency = backends.reduce(
+          function (worst, b) { return worstStatus(worst, b.status); }, null);
+        updateOverallBadge(chain);
+      } catch (e) {
+        // ignore parse errors
+      }
+    });
 
-        const data = JSON.parse(evt.detail.xhr.responseText);
-        const p50 = (data.p50_ms ?? NaN).toFixed(1);
-        const p95 = (data.p95_ms ?? NaN).toFixed(1);
-        const status = data.status || 'down';
-
-        document.getElementById('lat-p50').textContent = p50;
-        document.getElementById('lat-p95').textContent = p95;
-
-        const badge = document.getElementById('status-badge');
-        badge.classList.remove('ok', 'warn', 'down');
-        badge.classList.add(status);
-        badge.textContent = (status === 'ok' ? 'OK' : status === 'warn' ? 'WARN' : 'DOWN');
-
-        document.getElementById('lat-ts').textContent =
-          'Last probe: ' + new Date().toLocaleTimeString();
+    // Parse health JSON and update the health badge/summary
+    document.body.addEventListener('htmx:afterOnLoad', function (evt) {
+      try {
+        const url = evt.detail.xhr.responseURL || '';
+        if (!url.includes('/api/health')) return;
+        const chain = chainOf(evt.detail.xhr);
+        if (!chain) return;
+
+        const h = JSON.parse(evt.detail.xhr.responseText);
+
+        const badge = document.getElementById('health-badge-' + chain);
+        if (badge) {
+          badge.classList.remove('ok', 'warn', 'down');
+          badge.classList.add(h.status);
+          badge.textContent = h.status.toUpperCase();
+        }
+
+        const summary = document.getElementById('health-summary-' + chain);
+        if (summary) {
+          summary.textContent =
+            'syncing: ' + h.syncing +
+            ', peers: ' + (h.peers ?? '—') +
+            ', head age: ' + (h.head_age_secs ?? '—') + 's';
+        }
+
+        chainSignals[chain] = chainSignals[chain] || {};
+        chainSignals[chain].health = h.status;
+        updateOverallBadge(chain);
       } catch (e) {
         // ignore parse errors
       }
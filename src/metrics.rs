@@ -0,0 +1,94 @@
+//! Prometheus text-exposition endpoint, in the same spirit as Garage's
+//! admin metrics server: external monitoring can scrape block height and
+//! per-backend latency instead of only seeing status in the HTML badge.
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use prometheus::{Encoder, GaugeVec, IntCounterVec, Opts, Registry, TextEncoder};
+
+use crate::AppState;
+
+pub struct Metrics {
+    registry: Registry,
+    latest_block: GaugeVec,
+    probe_latency_ms: GaugeVec,
+    backend_up: GaugeVec,
+    probe_failures_total: IntCounterVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let latest_block = GaugeVec::new(
+            Opts::new("web3_latest_block", "Latest block number observed, per chain"),
+            &["chain"],
+        )
+        .expect("metric");
+        let probe_latency_ms = GaugeVec::new(
+            Opts::new(
+                "web3_probe_latency_ms",
+                "Per-backend probe latency; quantile 0.5 is the peak-EWMA average, 0.95 is the decayed peak",
+            ),
+            &["url", "quantile"],
+        )
+        .expect("metric");
+        let backend_up = GaugeVec::new(
+            Opts::new("web3_backend_up", "1 if the backend is reachable and not degraded"),
+            &["url"],
+        )
+        .expect("metric");
+        let probe_failures_total = IntCounterVec::new(
+            Opts::new("web3_probe_failures_total", "Count of failed latency probes per backend"),
+            &["url", "kind"],
+        )
+        .expect("metric");
+
+        registry.register(Box::new(latest_block.clone())).expect("register");
+        registry.register(Box::new(probe_latency_ms.clone())).expect("register");
+        registry.register(Box::new(backend_up.clone())).expect("register");
+        registry.register(Box::new(probe_failures_total.clone())).expect("register");
+
+        Metrics {
+            registry,
+            latest_block,
+            probe_latency_ms,
+            backend_up,
+            probe_failures_total,
+        }
+    }
+
+    pub fn set_latest_block(&self, chain: &str, block_num: u64) {
+        self.latest_block.with_label_values(&[chain]).set(block_num as f64);
+    }
+
+    /// Update the per-backend gauges/counters from the same probe that
+    /// feeds the peak-EWMA estimator in `node_latency`. `fail_kind` is
+    /// `RpcCallError::kind()` ("transport" | "rpc") when the underlying call
+    /// failed, so `web3_probe_failures_total` can tell "node unreachable"
+    /// apart from "node answered with a JSON-RPC error".
+    pub fn observe_probe(&self, url: &str, ewma_ms: f64, peak_ms: f64, up: bool, fail_kind: Option<&str>) {
+        self.probe_latency_ms.with_label_values(&[url, "0.5"]).set(ewma_ms);
+        self.probe_latency_ms.with_label_values(&[url, "0.95"]).set(peak_ms);
+        self.backend_up.with_label_values(&[url]).set(if up { 1.0 } else { 0.0 });
+        if let Some(kind) = fail_kind {
+            self.probe_failures_total.with_label_values(&[url, kind]).inc();
+        }
+    }
+
+    fn encode(&self) -> String {
+        let encoder = TextEncoder::new();
+        let metric_families = self.registry.gather();
+        let mut buf = Vec::new();
+        encoder.encode(&metric_families, &mut buf).expect("encode");
+        String::from_utf8(buf).expect("utf8")
+    }
+}
+
+pub async fn metrics_handler(State(state): State<AppState>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        state.metrics.encode(),
+    )
+}
@@ -0,0 +1,167 @@
+//! Real-time block push over SSE, backed by a single `eth_subscribe`
+//! ("newHeads") websocket connection to the upstream node.
+//!
+//! One background task per configured chain (spawned once from `main`)
+//! holds the websocket subscription open and rebroadcasts every new head to
+//! all connected browsers via a [`tokio::sync::broadcast`] channel.
+//! `/api/stream?chain=...` subscribes clients to that chain's channel. If
+//! the ranked backend doesn't speak websocket (or the subscription drops),
+//! `/api/stream` falls back to the same polling cadence the page already
+//! uses for `/api/latest-block`.
+
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::{Stream, StreamExt};
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{AppState, ChainQuery, ChainState};
+use std::sync::Arc;
+
+const BROADCAST_CAPACITY: usize = 64;
+/// Cadence used when falling back to polling because the websocket
+/// subscription to the best-ranked backend is not up -- matches the page's
+/// existing htmx poll timer.
+const POLL_FALLBACK_INTERVAL: Duration = Duration::from_secs(15);
+/// How long to wait before retrying a dropped/failed subscription.
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_secs(5);
+
+pub struct HeadStream {
+    tx: broadcast::Sender<String>,
+    ws_available: AtomicBool,
+}
+
+impl HeadStream {
+    pub fn new() -> Self {
+        let (tx, _rx) = broadcast::channel(BROADCAST_CAPACITY);
+        HeadStream {
+            tx,
+            ws_available: AtomicBool::new(false),
+        }
+    }
+}
+
+fn ws_url(http_url: &str) -> String {
+    http_url
+        .replacen("https://", "wss://", 1)
+        .replacen("http://", "ws://", 1)
+}
+
+/// Background task: keep a single `newHeads` subscription open against the
+/// best-ranked backend for this chain and broadcast each head to connected
+/// SSE clients. Reconnects with a fixed backoff on any disconnect or
+/// failure.
+pub async fn run_head_subscriber(state: AppState, chain: Arc<ChainState>) {
+    loop {
+        let backend_url = chain.backends.ranked().into_iter().next().map(|b| b.url.clone());
+        let Some(http_url) = backend_url else {
+            tokio::time::sleep(RESUBSCRIBE_BACKOFF).await;
+            continue;
+        };
+        let url = ws_url(&http_url);
+
+        match tokio_tungstenite::connect_async(&url).await {
+            Ok((ws, _)) => {
+                chain.stream.ws_available.store(true, Ordering::Relaxed);
+                forward_new_heads(ws, &state, &chain).await;
+            }
+            Err(err) => {
+                tracing::warn!(chain = %chain.config.name, %url, %err, "newHeads subscription unavailable, falling back to polling");
+            }
+        }
+
+        chain.stream.ws_available.store(false, Ordering::Relaxed);
+        tokio::time::sleep(RESUBSCRIBE_BACKOFF).await;
+    }
+}
+
+async fn forward_new_heads(
+    mut ws: tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>,
+    state: &AppState,
+    chain: &Arc<ChainState>,
+) {
+    use futures_util::SinkExt;
+
+    let subscribe = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_subscribe",
+        "params": ["newHeads"],
+    });
+    if ws.send(Message::Text(subscribe.to_string())).await.is_err() {
+        return;
+    }
+
+    while let Some(msg) = ws.next().await {
+        let Ok(Message::Text(text)) = msg else { continue };
+        let Ok(parsed) = serde_json::from_str::<Value>(&text) else { continue };
+        let Some(head) = parsed.pointer("/params/result") else { continue };
+        let Some(block_str) = head.get("number").and_then(Value::as_str) else { continue };
+        let block_num = crate::hex_to_u64(block_str).unwrap_or(0);
+        state.metrics.set_latest_block(&chain.config.name, block_num);
+
+        // same block fields as /api/latest-block (blockNumberHex, blockNumber,
+        // chain) so the block card shows the same data whether it came from
+        // SSE or polling; unlike /api/latest-block there is no `backend` field
+        let payload = json!({
+            "blockNumberHex": block_str,
+            "blockNumber": block_num,
+            "chain": chain.config.name,
+        });
+        // ignore send errors: they just mean no SSE clients are subscribed
+        let _ = chain.stream.tx.send(payload.to_string());
+    }
+}
+
+/// How often an already-connected SSE client re-checks `ws_available` while
+/// waiting on the broadcast channel, so a live stream notices a dropped
+/// websocket subscription instead of just going quiet.
+const WS_AVAILABILITY_POLL: Duration = Duration::from_secs(1);
+
+pub async fn stream_handler(
+    State(state): State<AppState>,
+    Query(q): Query<ChainQuery>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let chain = state.resolve_chain(q.chain.as_deref());
+
+    let Some(chain) = chain else {
+        // unknown chain: emit nothing rather than guessing a default
+        return Sse::new(futures_util::stream::empty().boxed()).keep_alive(KeepAlive::default());
+    };
+
+    // A single receiver for this chain's broadcast channel, reused across
+    // the whole connection: `run_head_subscriber` keeps the same `tx` alive
+    // across reconnects, it just stops (and later resumes) sending while
+    // `ws_available` is false. Each iteration re-checks that flag, so a
+    // subscription that drops mid-stream switches this already-connected
+    // client over to polling automatically, and switches back once the
+    // websocket comes back up -- not just new connections made after the
+    // flip.
+    let rx = chain.stream.tx.subscribe();
+    let events = futures_util::stream::unfold((state, chain, rx), |(state, chain, mut rx)| async move {
+        loop {
+            if chain.stream.ws_available.load(Ordering::Relaxed) {
+                tokio::select! {
+                    msg = rx.recv() => {
+                        match msg {
+                            Ok(payload) => return Some((Ok(Event::default().data(payload)), (state, chain, rx))),
+                            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                            Err(broadcast::error::RecvError::Closed) => return None,
+                        }
+                    }
+                    _ = tokio::time::sleep(WS_AVAILABILITY_POLL) => continue,
+                }
+            } else {
+                tokio::time::sleep(POLL_FALLBACK_INTERVAL).await;
+                let value = crate::fetch_latest_block(state.clone(), chain.clone()).await;
+                return Some((Ok(Event::default().data(value.to_string())), (state, chain, rx)));
+            }
+        }
+    });
+    Sse::new(events.boxed()).keep_alive(KeepAlive::default())
+}